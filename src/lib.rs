@@ -14,17 +14,33 @@
 //!
 //! This crate is nightly only and experimental. Breaking changes can occur at
 //! any time, if changes in Rust require it.
+//!
+//! # `no_std`
+//!
+//! The crate is `no_std`-compatible when the `libm` feature is enabled: the
+//! arithmetic intrinsics live in `core::intrinsics`, so with `std` off only the
+//! [`round`][FF64::round] method and the [`Float`] methods need a floating point
+//! runtime. Build with `--no-default-features --features libm` and those
+//! delegate to the optional [`libm`] dependency instead of `std`, following the
+//! route num-traits took to revive `Float` in `no_std`.
 #![feature(core_intrinsics)]
+#![allow(internal_features)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::{
-    intrinsics::{fadd_fast, fdiv_fast, fmul_fast, frem_fast, fsub_fast},
+use core::{
+    intrinsics::{
+        fadd_algebraic, fadd_fast, fdiv_algebraic, fdiv_fast, fmul_algebraic, fmul_fast,
+        fmuladdf32, fmuladdf64, frem_algebraic, frem_fast, fsub_algebraic, fsub_fast,
+    },
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign, Neg},
 };
 
 use num_derive::{Float, One, Zero, Num, NumCast, ToPrimitive, FromPrimitive};
 
+pub mod reductions;
+
 macro_rules! float_wrapper {
-    ($name: ident ($t: ty)) => {
+    ($name: ident ($t: ty), $libm_round: ident) => {
         #[derive(Copy, Clone, PartialEq, PartialOrd, Default, ToPrimitive, FromPrimitive, Num, NumCast, Zero, One, Float)]
         #[repr(transparent)]
         pub struct $name(pub $t);
@@ -63,37 +79,51 @@ macro_rules! float_wrapper {
         impl $name {
             #[inline(always)]
             pub fn round(self) -> Self {
-                self.0.round().into()
+                #[cfg(feature = "std")]
+                {
+                    self.0.round().into()
+                }
+                #[cfg(all(not(feature = "std"), feature = "libm"))]
+                {
+                    libm::$libm_round(self.0).into()
+                }
+                #[cfg(not(any(feature = "std", feature = "libm")))]
+                compile_error!("enable either the `std` or `libm` feature");
             }
         }
     };
 }
 
-float_wrapper! { FF32(f32) }
-float_wrapper! { FF64(f64) }
+float_wrapper! { FF32(f32), roundf }
+float_wrapper! { FF64(f64), round }
 
+// The intrinsic set is parameterized so the same expansion serves both the
+// all-or-nothing `fast` wrappers (which wrap the `unsafe` intrinsics) and the
+// `algebraic` wrappers (whose intrinsics are safe). The third argument is the
+// literal `unsafe` or `safe`, selecting whether the intrinsic call is wrapped in
+// an `unsafe` block via the `@call` helper arms below.
 macro_rules! impl_op {
-    ($($name:ident, $method:ident, $intrins:ident;)*) => {
+    (@call unsafe $intrins:ident ($($arg:expr),*)) => { unsafe { $intrins($($arg),*) } };
+    (@call safe $intrins:ident ($($arg:expr),*)) => { $intrins($($arg),*) };
+    ($w32:ident ($t32:ty), $w64:ident ($t64:ty), $safe:tt; $($name:ident, $method:ident, $intrins:ident;)*) => {
         $(
-            impl $name<f32> for FF32 {
+            impl $name<$t32> for $w32 {
                 type Output = Self;
                 #[inline(always)]
-                fn $method(self, rhs: f32) -> Self::Output {
-                    unsafe {
-                        FF32($intrins(self.0, rhs))
-                    }
+                fn $method(self, rhs: $t32) -> Self::Output {
+                    $w32(impl_op!(@call $safe $intrins(self.0, rhs)))
                 }
             }
 
-            impl $name<FF32> for f32 {
-                type Output = FF32;
+            impl $name<$w32> for $t32 {
+                type Output = $w32;
                 #[inline(always)]
-                fn $method(self, rhs: FF32) -> Self::Output {
-                    FF32(self).$method(rhs.0)
+                fn $method(self, rhs: $w32) -> Self::Output {
+                    $w32(self).$method(rhs.0)
                 }
             }
 
-            impl $name for FF32 {
+            impl $name for $w32 {
                 type Output = Self;
                 #[inline(always)]
                 fn $method(self, rhs: Self) -> Self::Output {
@@ -101,25 +131,23 @@ macro_rules! impl_op {
                 }
             }
 
-            impl $name<f64> for FF64 {
+            impl $name<$t64> for $w64 {
                 type Output = Self;
                 #[inline(always)]
-                fn $method(self, rhs: f64) -> Self::Output {
-                    unsafe {
-                        FF64($intrins(self.0, rhs))
-                    }
+                fn $method(self, rhs: $t64) -> Self::Output {
+                    $w64(impl_op!(@call $safe $intrins(self.0, rhs)))
                 }
             }
 
-            impl $name<FF64> for f64 {
-                type Output = FF64;
+            impl $name<$w64> for $t64 {
+                type Output = $w64;
                 #[inline(always)]
-                fn $method(self, rhs: FF64) -> Self::Output {
-                    FF64(self).$method(rhs.0)
+                fn $method(self, rhs: $w64) -> Self::Output {
+                    $w64(self).$method(rhs.0)
                 }
             }
 
-            impl $name for FF64 {
+            impl $name for $w64 {
                 type Output = Self;
                 #[inline(always)]
                 fn $method(self, rhs: Self) -> Self::Output {
@@ -132,23 +160,23 @@ macro_rules! impl_op {
 }
 
 macro_rules! impl_assignop {
-    ($($name:ident, $method:ident, $intrins:ident;)*) => {
+    ($w32:ident, $w64:ident, $($name:ident, $method:ident, $binop:ident, $binop_method:ident;)*) => {
         $(
-            impl<Rhs> $name<Rhs> for FF32
-                where Self: Add<Rhs, Output=Self> + Copy,
+            impl<Rhs> $name<Rhs> for $w32
+                where Self: $binop<Rhs, Output=Self> + Copy,
             {
                 #[inline(always)]
                 fn $method(&mut self, rhs: Rhs) {
-                    *self = *self + rhs
+                    *self = $binop::$binop_method(*self, rhs)
                 }
             }
 
-            impl<Rhs> $name<Rhs> for FF64
-            where Self: Add<Rhs, Output=Self> + Copy,
+            impl<Rhs> $name<Rhs> for $w64
+            where Self: $binop<Rhs, Output=Self> + Copy,
         {
             #[inline(always)]
             fn $method(&mut self, rhs: Rhs) {
-                *self = *self + rhs
+                *self = $binop::$binop_method(*self, rhs)
             }
         }
         )*
@@ -156,6 +184,7 @@ macro_rules! impl_assignop {
 }
 
 impl_op! {
+    FF32 (f32), FF64 (f64), unsafe;
     Add, add, fadd_fast;
     Sub, sub, fsub_fast;
     Mul, mul, fmul_fast;
@@ -163,25 +192,112 @@ impl_op! {
     Rem, rem, frem_fast;
 }
 
-impl_assignop! {
-    AddAssign, add_assign, fadd_fast;
-    SubAssign, sub_assign, fsub_fast;
-    MulAssign, mul_assign, fmul_fast;
-    DivAssign, div_assign, fdiv_fast;
-    RemAssign, rem_assign, frem_fast;
+/// Generate the three borrowed-operand variants of a binary operator
+/// (`&Lhs op Rhs`, `Lhs op &Rhs`, `&Lhs op &Rhs`) by delegating to the existing
+/// by-value impl. Mirrors the `forward_ref_binop!` pattern from the numeric
+/// crates so code holding `&FF64` need not dereference by hand.
+macro_rules! fast_ref_binop {
+    ($imp:ident, $method:ident, $lhs:ty, $rhs:ty) => {
+        impl $imp<&$rhs> for $lhs {
+            type Output = <$lhs as $imp<$rhs>>::Output;
+            #[inline(always)]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+
+        impl $imp<$rhs> for &$lhs {
+            type Output = <$lhs as $imp<$rhs>>::Output;
+            #[inline(always)]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+
+        impl $imp<&$rhs> for &$lhs {
+            type Output = <$lhs as $imp<$rhs>>::Output;
+            #[inline(always)]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
 }
 
-use std::fmt;
-macro_rules! impl_format {
-    ($($name:ident)+) => {
+/// Generate the borrowed-operand variant of a unary operator (`-&FF64`).
+macro_rules! fast_ref_unop {
+    ($imp:ident, $method:ident, $t:ty) => {
+        impl $imp for &$t {
+            type Output = <$t as $imp>::Output;
+            #[inline(always)]
+            fn $method(self) -> Self::Output {
+                $imp::$method(*self)
+            }
+        }
+    };
+}
+
+macro_rules! fast_ref_binops {
+    ($($imp:ident, $method:ident;)*) => {
         $(
-            impl fmt::$name for FF32 {
-                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                    self.0.fmt(f)
+            fast_ref_binop!($imp, $method, FF32, FF32);
+            fast_ref_binop!($imp, $method, FF32, f32);
+            fast_ref_binop!($imp, $method, f32, FF32);
+            fast_ref_binop!($imp, $method, FF64, FF64);
+            fast_ref_binop!($imp, $method, FF64, f64);
+            fast_ref_binop!($imp, $method, f64, FF64);
+        )*
+    };
+}
+
+fast_ref_binops! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+    Rem, rem;
+}
+
+fast_ref_unop!(Neg, neg, FF32);
+fast_ref_unop!(Neg, neg, FF64);
+
+macro_rules! impl_mul_add {
+    ($($name:ident ($t:ty), $intrins:ident;)*) => {
+        $(
+            impl $name {
+                /// Multiply-add: computes `self * a + b` via the `fmuladd`
+                /// intrinsic, which fuses the two into one FMA with a single
+                /// rounding where the target supports it; otherwise the multiply
+                /// and the add are each rounded. Accepts `f32`/`f64` or the
+                /// matching wrapper for `a`/`b`.
+                #[inline(always)]
+                pub fn mul_add<A: Into<$t>, B: Into<$t>>(self, a: A, b: B) -> Self {
+                    $name($intrins(self.0, a.into(), b.into()))
                 }
             }
+        )*
+    }
+}
 
-            impl fmt::$name for FF64 {
+impl_mul_add! {
+    FF32 (f32), fmuladdf32;
+    FF64 (f64), fmuladdf64;
+}
+
+impl_assignop! {
+    FF32, FF64,
+    AddAssign, add_assign, Add, add;
+    SubAssign, sub_assign, Sub, sub;
+    MulAssign, mul_assign, Mul, mul;
+    DivAssign, div_assign, Div, div;
+    RemAssign, rem_assign, Rem, rem;
+}
+
+use core::fmt;
+macro_rules! impl_format {
+    ($t:ident; $($name:ident)+) => {
+        $(
+            impl fmt::$name for $t {
                 fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                     self.0.fmt(f)
                 }
@@ -190,7 +306,175 @@ macro_rules! impl_format {
     }
 }
 
-impl_format!(Debug Display LowerExp UpperExp);
+impl_format!(FF32; Debug Display LowerExp UpperExp);
+impl_format!(FF64; Debug Display LowerExp UpperExp);
+
+// Wrappers over the `algebraic` intrinsics. These enable the algebraic
+// relaxations (`reassoc`, `contract`, `arcp`, `nsz`, `afn`) — allowing
+// vectorization and FMA formation — but not the `nnan`/`ninf` assumptions of
+// `fast`, so NaN/Inf inputs are not undefined: a middle ground between IEEE-strict floats
+// and the all-or-nothing `fast` flag. Crucially the algebraic intrinsics are
+// *not* `unsafe`, so the generated ops carry no `unsafe` block.
+float_wrapper! { Algebraic32(f32), roundf }
+float_wrapper! { Algebraic64(f64), round }
+
+impl_op! {
+    Algebraic32 (f32), Algebraic64 (f64), safe;
+    Add, add, fadd_algebraic;
+    Sub, sub, fsub_algebraic;
+    Mul, mul, fmul_algebraic;
+    Div, div, fdiv_algebraic;
+    Rem, rem, frem_algebraic;
+}
+
+impl_assignop! {
+    Algebraic32, Algebraic64,
+    AddAssign, add_assign, Add, add;
+    SubAssign, sub_assign, Sub, sub;
+    MulAssign, mul_assign, Mul, mul;
+    DivAssign, div_assign, Div, div;
+    RemAssign, rem_assign, Rem, rem;
+}
+
+impl_format!(Algebraic32; Debug Display LowerExp UpperExp);
+impl_format!(Algebraic64; Debug Display LowerExp UpperExp);
+
+// Half-precision wrapper, gated on the optional `half` dependency. There are no
+// LLVM fast-math intrinsics for 16-bit floats, so each op widens to `f32`, runs
+// the corresponding fast intrinsic, then narrows back. That still lets the
+// `reassoc`/`contract` flags fire in the mixed-precision accumulation loops
+// common in ML inference.
+#[cfg(feature = "f16")]
+mod ff16 {
+    use super::*;
+    use half::f16;
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Default)]
+    #[repr(transparent)]
+    pub struct FF16(pub f16);
+
+    impl FF16 {
+        /// Get the inner value
+        #[inline(always)]
+        pub fn get(self) -> f16 {
+            self.0
+        }
+    }
+
+    impl From<f16> for FF16 {
+        #[inline(always)]
+        fn from(other: f16) -> Self {
+            FF16(other)
+        }
+    }
+
+    impl From<FF16> for f16 {
+        #[inline(always)]
+        fn from(other: FF16) -> f16 {
+            other.get()
+        }
+    }
+
+    impl Neg for FF16 {
+        type Output = FF16;
+
+        #[inline(always)]
+        fn neg(self) -> Self::Output {
+            FF16(self.0.neg())
+        }
+    }
+
+    macro_rules! impl_ff16_op {
+        ($($name:ident, $method:ident, $intrins:ident;)*) => {
+            $(
+                impl $name<f16> for FF16 {
+                    type Output = Self;
+                    #[inline(always)]
+                    fn $method(self, rhs: f16) -> Self::Output {
+                        let r = unsafe { $intrins(self.0.to_f32(), rhs.to_f32()) };
+                        FF16(f16::from_f32(r))
+                    }
+                }
+
+                impl $name<FF16> for f16 {
+                    type Output = FF16;
+                    #[inline(always)]
+                    fn $method(self, rhs: FF16) -> Self::Output {
+                        FF16(self).$method(rhs.0)
+                    }
+                }
+
+                impl $name for FF16 {
+                    type Output = Self;
+                    #[inline(always)]
+                    fn $method(self, rhs: Self) -> Self::Output {
+                        self.$method(rhs.0)
+                    }
+                }
+            )*
+        }
+    }
+
+    impl_ff16_op! {
+        Add, add, fadd_fast;
+        Sub, sub, fsub_fast;
+        Mul, mul, fmul_fast;
+        Div, div, fdiv_fast;
+        Rem, rem, frem_fast;
+    }
+
+    macro_rules! impl_ff16_assignop {
+        ($($name:ident, $method:ident, $binop:ident, $binop_method:ident;)*) => {
+            $(
+                impl<Rhs> $name<Rhs> for FF16
+                    where Self: $binop<Rhs, Output=Self> + Copy,
+                {
+                    #[inline(always)]
+                    fn $method(&mut self, rhs: Rhs) {
+                        *self = $binop::$binop_method(*self, rhs)
+                    }
+                }
+            )*
+        }
+    }
+
+    impl_ff16_assignop! {
+        AddAssign, add_assign, Add, add;
+        SubAssign, sub_assign, Sub, sub;
+        MulAssign, mul_assign, Mul, mul;
+        DivAssign, div_assign, Div, div;
+        RemAssign, rem_assign, Rem, rem;
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn assign_ops_use_matching_operator() {
+            let mut a = FF16(f16::from_f32(5.));
+            a -= FF16(f16::from_f32(2.));
+            assert_eq!(a, FF16(f16::from_f32(3.)));
+        }
+    }
+
+    macro_rules! impl_ff16_format {
+        ($($name:ident)+) => {
+            $(
+                impl fmt::$name for FF16 {
+                    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        self.0.fmt(f)
+                    }
+                }
+            )+
+        }
+    }
+
+    impl_ff16_format!(Debug Display LowerExp UpperExp);
+}
+
+#[cfg(feature = "f16")]
+pub use ff16::FF16;
 
 #[allow(unused)]
 #[cfg(test)]
@@ -210,23 +494,52 @@ mod tests {
         test_op!(+ - * / %);
     }
 
-    // for demonstration purposes
-    fn fast_sum(xs: &[f64]) -> f64 {
-        xs.iter()
-            .map(|&x| FF64(x))
-            .fold(FF64(0.), |acc, x| acc + x)
-            .get()
+    #[test]
+    fn each_algebraic_op() {
+        macro_rules! test_alg {
+            ($($op:tt)+) => {
+                $(
+                    assert_eq!(Algebraic32(2.) $op Algebraic32(1.), Algebraic32(2. $op 1.));
+                    assert_eq!(Algebraic64(2.) $op Algebraic64(1.), Algebraic64(2. $op 1.));
+                )+
+            }
+        }
+        test_alg!(+ - * / %);
+    }
+
+    #[test]
+    fn assign_ops_use_matching_operator() {
+        let mut a = FF64(5.);
+        a -= FF64(2.);
+        assert_eq!(a, FF64(3.));
+        let mut b = Algebraic64(5.);
+        b -= Algebraic64(2.);
+        assert_eq!(b, Algebraic64(3.));
+        let mut c = Algebraic32(6.);
+        c /= Algebraic32(2.);
+        assert_eq!(c, Algebraic32(3.));
     }
 
-    // for demonstration purposes
-    fn fast_dot(xs: &[f64], ys: &[f64]) -> f64 {
-        xs.iter()
-            .zip(ys)
-            .fold(FF64(0.), |acc, (&x, &y)| acc + FF64(x) * FF64(y))
-            .get()
+    #[test]
+    fn mul_add_each_rhs() {
+        // scalar, wrapper, and mixed right-hand sides all lower to fmuladd.
+        assert_eq!(FF32(2.).mul_add(3.0f32, 1.0f32), FF32(7.));
+        assert_eq!(FF32(2.).mul_add(FF32(3.), FF32(1.)), FF32(7.));
+        assert_eq!(FF32(2.).mul_add(FF32(3.), 1.0f32), FF32(7.));
+        assert_eq!(FF64(2.).mul_add(3.0f64, 1.0f64), FF64(7.));
+        assert_eq!(FF64(2.).mul_add(FF64(3.), FF64(1.)), FF64(7.));
+        assert_eq!(FF64(2.).mul_add(FF64(3.), 1.0f64), FF64(7.));
     }
 
-    fn regular_sum(xs: &[f64]) -> f64 {
-        xs.iter().map(|&x| x).fold(0., |acc, x| acc + x)
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ref_operands() {
+        let a = FF64(2.);
+        let b = FF64(1.);
+        assert_eq!(&a + &b, FF64(3.));
+        assert_eq!(a + &b, FF64(3.));
+        assert_eq!(&a + b, FF64(3.));
+        assert_eq!(&a + 1.0f64, FF64(3.));
+        assert_eq!(-&a, FF64(-2.));
     }
 }
@@ -0,0 +1,110 @@
+//! Fast-math reductions (sum, dot, norm) built on the `FF32`/`FF64` wrappers.
+//!
+//! A naive `sum += x` loop is forced to accumulate strictly left-to-right and
+//! therefore cannot be vectorized. By running the accumulation through the
+//! wrapper ops — which emit the `fadd_fast`/`fmul_fast` intrinsics — the
+//! `reassoc` flag lets the compiler split the reduction into independent lanes
+//! and add them back up at the end. These helpers are thin wrappers around the
+//! wrapper arithmetic so they actually lower to the fast intrinsics.
+
+use num_traits::Zero;
+use core::ops::Add;
+
+use crate::{FF32, FF64};
+
+/// Fold an iterator of wrappers into their fast-math sum.
+///
+/// Generic over `FF32`/`FF64` (anything that is `Zero` and adds to itself), so
+/// the same reassociation win is available whatever the precision.
+#[inline]
+pub fn fast_fold<T, I>(iter: I) -> T
+where
+    I: IntoIterator<Item = T>,
+    T: Zero + Add<Output = T>,
+{
+    iter.into_iter().fold(T::zero(), |acc, x| acc + x)
+}
+
+/// Sum a slice of `f64` using fast-math addition.
+#[inline]
+pub fn fast_sum(xs: &[f64]) -> f64 {
+    fast_fold(xs.iter().map(|&x| FF64(x))).get()
+}
+
+/// Sum a slice of `f32` using fast-math addition.
+#[inline]
+pub fn fast_sum_f32(xs: &[f32]) -> f32 {
+    fast_fold(xs.iter().map(|&x| FF32(x))).get()
+}
+
+/// Dot product of two `f64` slices using fast-math multiply/add.
+///
+/// Only the elements in the overlap of the two slices contribute.
+#[inline]
+pub fn fast_dot(xs: &[f64], ys: &[f64]) -> f64 {
+    xs.iter()
+        .zip(ys)
+        .fold(FF64(0.), |acc, (&x, &y)| acc + FF64(x) * FF64(y))
+        .get()
+}
+
+/// Dot product of two `f32` slices using fast-math multiply/add.
+#[inline]
+pub fn fast_dot_f32(xs: &[f32], ys: &[f32]) -> f32 {
+    xs.iter()
+        .zip(ys)
+        .fold(FF32(0.), |acc, (&x, &y)| acc + FF32(x) * FF32(y))
+        .get()
+}
+
+/// Squared Euclidean norm of an `f64` slice (the dot product with itself).
+#[inline]
+pub fn fast_norm_sqr(xs: &[f64]) -> f64 {
+    fast_dot(xs, xs)
+}
+
+/// Squared Euclidean norm of an `f32` slice.
+#[inline]
+pub fn fast_norm_sqr_f32(xs: &[f32]) -> f32 {
+    fast_dot_f32(xs, xs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regular_sum(xs: &[f64]) -> f64 {
+        xs.iter().fold(0., |acc, &x| acc + x)
+    }
+
+    fn regular_dot(xs: &[f64], ys: &[f64]) -> f64 {
+        xs.iter().zip(ys).map(|(&x, &y)| x * y).sum()
+    }
+
+    #[test]
+    fn sum_matches_naive() {
+        let xs: Vec<f64> = (0..1000).map(|i| i as f64 * 0.5).collect();
+        let diff = (fast_sum(&xs) - regular_sum(&xs)).abs();
+        assert!(diff <= 1e-6 * regular_sum(&xs).abs(), "diff = {diff}");
+    }
+
+    #[test]
+    fn dot_matches_naive() {
+        let xs: Vec<f64> = (0..1000).map(|i| i as f64 * 0.25).collect();
+        let ys: Vec<f64> = (0..1000).map(|i| (i as f64).sin()).collect();
+        let diff = (fast_dot(&xs, &ys) - regular_dot(&xs, &ys)).abs();
+        assert!(diff <= 1e-6 * regular_dot(&xs, &ys).abs().max(1.0), "diff = {diff}");
+    }
+
+    #[test]
+    fn norm_sqr_is_self_dot() {
+        let xs = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(fast_norm_sqr(&xs), fast_dot(&xs, &xs));
+    }
+
+    #[test]
+    fn fold_sums_wrappers() {
+        let s = fast_fold([FF64(1.), FF64(2.), FF64(3.)]);
+        assert_eq!(s, FF64(6.));
+    }
+}